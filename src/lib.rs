@@ -42,11 +42,25 @@ extern crate alloc;
 #[cfg(feature = "std")]
 use std as alloc;
 
+use core::cell::Cell;
 use core::fmt;
+use core::mem;
 use core::ops::{Deref, DerefMut};
+use core::ptr;
+use alloc::rc::Rc;
 use alloc::sync::Arc;
 use core::sync::atomic::{AtomicUsize, Ordering};
 
+/// The bit of the `alive` counter that denotes an outstanding mutable borrow. The lower bits
+/// count the outstanding borrows: shared borrows while this bit is clear, and the mutable
+/// sub-borrows that share the lock while it is set.
+const MUTABLE_BORROW: usize = 1 << (usize::BITS - 1);
+
+/// The bit of the `alive` counter that denotes that the owning [`ScopedReference`] has been
+/// dropped. Only ever observed by [`WeakScopedBorrow::upgrade`], since no other code can reach
+/// the counter once the `ScopedReference` itself is gone.
+const DEAD: usize = 1 << (usize::BITS - 2);
+
 /// Allows for obtaining references with `'static` lifetime via runtime
 /// borrow checking.
 pub struct ScopedReference<'a, T: ?Sized> {
@@ -70,33 +84,115 @@ impl<'a, T: ?Sized> ScopedReference<'a, T> {
     }
 
     /// Obtains a dynamically-checked borrow to the current reference.
+    ///
+    /// # Panics/Aborts
+    ///
+    /// Aborts the process if the reference is already borrowed mutably. See [`Self::try_borrow`]
+    /// for a version that returns an error instead.
     pub fn borrow(&self) -> ScopedBorrow<T> {
-        match &self.reference {
-            Ok(r) => {
-                self.alive.fetch_add(1, Ordering::Release);
-                ScopedBorrow { pointer: *r as *const T, alive: self.alive.clone() }
-            },
-            Err(r) => {
-                if self.alive.load(Ordering::Acquire) == usize::MAX {
-                    panic_abort("Cannot borrow a lifetime mutably while it is already borrowed immutably.");
-                }
-                else {
-                    self.alive.fetch_add(1, Ordering::Release);
-                    ScopedBorrow { pointer: *r as *const T, alive: self.alive.clone() }
-                }
-            }
+        match self.try_borrow() {
+            Ok(borrow) => borrow,
+            Err(_) => panic_abort("Cannot borrow a lifetime immutably while it is already borrowed mutably.")
         }
     }
 
     /// Obtains a mutable dynamically-checked borrow to the current reference.
+    ///
+    /// # Panics/Aborts
+    ///
+    /// Aborts the process if the reference is already borrowed. See [`Self::try_borrow_mut`]
+    /// for a version that returns an error instead.
     pub fn borrow_mut(&mut self) -> ScopedBorrowMut<T> {
-        if self.alive.load(Ordering::Acquire) != 0 {
-            panic_abort("Scoped lifetime is already borrowed.")
+        match self.try_borrow_mut() {
+            Ok(borrow) => borrow,
+            Err(_) => panic_abort("Scoped lifetime is already borrowed.")
         }
-        else {
-            self.alive.store(usize::MAX, Ordering::Release);
-            ScopedBorrowMut { pointer: unsafe { self.reference.as_mut().map_err(|x| *x as *mut T).unwrap_err_unchecked() }, alive: self.alive.clone() }
+    }
+
+    /// Attempts to obtain a dynamically-checked borrow to the current reference, returning
+    /// an error rather than aborting if the reference is already borrowed mutably.
+    pub fn try_borrow(&self) -> Result<ScopedBorrow<T>, BorrowError> {
+        // Only increment while no mutable borrow is out, so that the low bits of a mutably
+        // borrowed counter count nothing but its mutable sub-borrows.
+        let mut previous = self.alive.load(Ordering::Acquire);
+        loop {
+            if previous & MUTABLE_BORROW != 0 {
+                return Err(BorrowError { _private: () });
+            }
+            if (previous + 1) & MUTABLE_BORROW != 0 {
+                panic_abort("Too many outstanding shared borrows of a scoped lifetime.");
+            }
+
+            match self.alive.compare_exchange_weak(previous, previous + 1, Ordering::AcqRel, Ordering::Acquire) {
+                Ok(_) => break,
+                Err(actual) => previous = actual
+            }
         }
+
+        let pointer = match &self.reference {
+            Ok(r) => *r as *const T,
+            Err(r) => *r as *const T
+        };
+        Ok(ScopedBorrow { pointer, alive: self.alive.clone() })
+    }
+
+    /// Attempts to obtain a mutable dynamically-checked borrow to the current reference, returning
+    /// an error rather than aborting if the reference is already borrowed.
+    pub fn try_borrow_mut(&mut self) -> Result<ScopedBorrowMut<T>, BorrowMutError> {
+        let pointer = match self.lock_mut(1) {
+            Some(pointer) => pointer,
+            None => return Err(BorrowMutError { _private: () })
+        };
+        Ok(ScopedBorrowMut { pointer, alive: self.alive.clone() })
+    }
+
+    /// Splits a mutable borrow into two disjoint mutable sub-borrows, each with an independent
+    /// runtime-checked lifetime. The reference remains locked - aborting if dropped - until
+    /// both returned borrows have been dropped.
+    ///
+    /// `f` must return genuinely non-aliasing sub-references into the borrowed value; this is
+    /// the same contract as std's `DormantMutRef`, which this API is modeled after.
+    ///
+    /// # Panics/Aborts
+    ///
+    /// Aborts the process if the reference is already borrowed.
+    pub fn borrow_mut_map<U: ?Sized, V: ?Sized>(&mut self, f: impl FnOnce(&mut T) -> (&mut U, &mut V)) -> (ScopedBorrowMut<U>, ScopedBorrowMut<V>) {
+        let pointer = match self.lock_mut(2) {
+            Some(pointer) => pointer,
+            None => panic_abort("Scoped lifetime is already borrowed.")
+        };
+        let (a, b) = f(unsafe { &mut *pointer });
+        (ScopedBorrowMut { pointer: a as *mut U, alive: self.alive.clone() },
+         ScopedBorrowMut { pointer: b as *mut V, alive: self.alive.clone() })
+    }
+
+    /// Splits a mutable borrow into `N` disjoint mutable sub-borrows, each with an independent
+    /// runtime-checked lifetime. The reference remains locked - aborting if dropped - until
+    /// every returned borrow has been dropped.
+    ///
+    /// `f` must return genuinely non-aliasing sub-references into the borrowed value; this is
+    /// the same contract as std's `DormantMutRef`, which this API is modeled after.
+    ///
+    /// # Panics/Aborts
+    ///
+    /// Aborts the process if the reference is already borrowed.
+    pub fn split<const N: usize, U: ?Sized>(&mut self, f: impl FnOnce(&mut T) -> [&mut U; N]) -> [ScopedBorrowMut<U>; N] {
+        const { assert!(N > 0, "ScopedReference::split requires at least one sub-borrow; an empty split would lock the reference with no borrow left to unlock it") };
+
+        let pointer = match self.lock_mut(N) {
+            Some(pointer) => pointer,
+            None => panic_abort("Scoped lifetime is already borrowed.")
+        };
+        f(unsafe { &mut *pointer }).map(|r| ScopedBorrowMut { pointer: r as *mut U, alive: self.alive.clone() })
+    }
+
+    /// Attempts to exclusively lock the reference for `sub_borrows` outstanding mutable
+    /// sub-borrows, returning a pointer to the underlying value on success.
+    fn lock_mut(&mut self, sub_borrows: usize) -> Option<*mut T> {
+        if self.alive.compare_exchange(0, MUTABLE_BORROW | sub_borrows, Ordering::AcqRel, Ordering::Acquire).is_err() {
+            return None;
+        }
+        Some(unsafe { self.reference.as_mut().map_err(|x| *x as *mut T).unwrap_err_unchecked() })
     }
 }
 
@@ -114,7 +210,11 @@ impl<'a, T: ?Sized> fmt::Display for ScopedReference<'a, T> {
 
 impl<'a, T: ?Sized> Drop for ScopedReference<'a, T> {
     fn drop(&mut self) {
-        if self.alive.load(Ordering::Acquire) != 0 {
+        // Atomically transition 0 -> DEAD so that a concurrent `WeakScopedBorrow::upgrade`
+        // racing on another thread either wins outright (and we see its borrow and abort) or
+        // loses outright (and observes `DEAD` afterwards) - there is no window in which an
+        // upgrade can succeed and then have its borrow clobbered by this store.
+        if self.alive.compare_exchange(0, DEAD, Ordering::AcqRel, Ordering::Acquire).is_err() {
             panic_abort("Scoped lifetime was dropped while a borrow was out.")
         }
     }
@@ -147,6 +247,23 @@ impl<T: ?Sized> Clone for ScopedBorrow<T> {
     }
 }
 
+impl<T: ?Sized> ScopedBorrow<T> {
+    /// Projects this borrow to a subfield, preserving the runtime liveness guarantee on the original reference.
+    pub fn map<U: ?Sized>(self, f: impl FnOnce(&T) -> &U) -> ScopedBorrow<U> {
+        let pointer = f(&self) as *const U;
+        // SAFETY: `self` is forgotten immediately below, so its `Drop` impl never runs and
+        // the liveness count is left untouched - it is simply handed off to the new borrow.
+        let alive = unsafe { ptr::read(&self.alive) };
+        mem::forget(self);
+        ScopedBorrow { pointer, alive }
+    }
+
+    /// Creates a weak borrow that observes this reference's liveness without itself keeping it alive.
+    pub fn downgrade(&self) -> WeakScopedBorrow<T> {
+        WeakScopedBorrow { pointer: self.pointer, alive: self.alive.clone() }
+    }
+}
+
 impl<T: fmt::Debug + ?Sized> fmt::Debug for ScopedBorrow<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt::Debug::fmt(&**self, f)
@@ -162,6 +279,44 @@ impl<T: fmt::Display + ?Sized> fmt::Display for ScopedBorrow<T> {
 unsafe impl<T: ?Sized + Send> Send for ScopedBorrow<T> {}
 unsafe impl<T: ?Sized + Sync> Sync for ScopedBorrow<T> {}
 
+/// A weak companion to [`ScopedBorrow`] that observes a reference's liveness without itself
+/// contributing to the liveness count. This mirrors the relationship between `Arc` and a
+/// non-owning borrowing handle, such as the `ArcBorrow` type in the Rust-for-Linux `sync` module:
+/// a cache or observer table can hold onto a `WeakScopedBorrow` without forcing an abort if the
+/// owning [`ScopedReference`] is dropped first.
+pub struct WeakScopedBorrow<T: ?Sized> {
+    pointer: *const T,
+    alive: Arc<AtomicUsize>
+}
+
+impl<T: ?Sized> WeakScopedBorrow<T> {
+    /// Attempts to upgrade this weak borrow to a [`ScopedBorrow`], incrementing the liveness
+    /// count. Returns `None` if the owning [`ScopedReference`] has already been dropped, or if
+    /// it is currently borrowed mutably.
+    pub fn upgrade(&self) -> Option<ScopedBorrow<T>> {
+        let mut previous = self.alive.load(Ordering::Acquire);
+        loop {
+            if previous & (DEAD | MUTABLE_BORROW) != 0 {
+                return None;
+            }
+
+            match self.alive.compare_exchange_weak(previous, previous + 1, Ordering::AcqRel, Ordering::Acquire) {
+                Ok(_) => return Some(ScopedBorrow { pointer: self.pointer, alive: self.alive.clone() }),
+                Err(actual) => previous = actual
+            }
+        }
+    }
+}
+
+impl<T: ?Sized> Clone for WeakScopedBorrow<T> {
+    fn clone(&self) -> Self {
+        Self { pointer: self.pointer, alive: self.alive.clone() }
+    }
+}
+
+unsafe impl<T: ?Sized + Send> Send for WeakScopedBorrow<T> {}
+unsafe impl<T: ?Sized + Sync> Sync for WeakScopedBorrow<T> {}
+
 /// Represents a mutable borrow with a runtime-checked lifetime.
 pub struct ScopedBorrowMut<T: ?Sized> {
     pointer: *mut T,
@@ -182,9 +337,26 @@ impl<T: ?Sized> DerefMut for ScopedBorrowMut<T> {
     }
 }
 
+impl<T: ?Sized> ScopedBorrowMut<T> {
+    /// Projects this mutable borrow to a subfield, preserving the runtime liveness guarantee on the original reference.
+    pub fn map_mut<U: ?Sized>(mut self, f: impl FnOnce(&mut T) -> &mut U) -> ScopedBorrowMut<U> {
+        let pointer = f(&mut self) as *mut U;
+        // SAFETY: `self` is forgotten immediately below, so its `Drop` impl never runs and
+        // the mutable-borrow sentinel is left untouched - it is simply handed off to the new borrow.
+        let alive = unsafe { ptr::read(&self.alive) };
+        mem::forget(self);
+        ScopedBorrowMut { pointer, alive }
+    }
+}
+
 impl<T: ?Sized> Drop for ScopedBorrowMut<T> {
     fn drop(&mut self) {
-        self.alive.store(0, Ordering::Release);
+        // Each outstanding mutable borrow - including each half of a split borrow - holds one
+        // of the low bits. Only the last one to drop clears the mutable-borrow bit itself.
+        let previous = self.alive.fetch_sub(1, Ordering::Release);
+        if previous & !MUTABLE_BORROW == 1 {
+            self.alive.fetch_and(!MUTABLE_BORROW, Ordering::Release);
+        }
     }
 }
 
@@ -203,6 +375,36 @@ impl<T: fmt::Display + ?Sized> fmt::Display for ScopedBorrowMut<T> {
 unsafe impl<T: ?Sized + Send> Send for ScopedBorrowMut<T> {}
 unsafe impl<T: ?Sized + Sync> Sync for ScopedBorrowMut<T> {}
 
+/// The error returned by [`ScopedReference::try_borrow`] when the reference is already borrowed mutably.
+#[derive(Debug)]
+pub struct BorrowError {
+    _private: ()
+}
+
+impl fmt::Display for BorrowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "already mutably borrowed")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BorrowError {}
+
+/// The error returned by [`ScopedReference::try_borrow_mut`] when the reference is already borrowed.
+#[derive(Debug)]
+pub struct BorrowMutError {
+    _private: ()
+}
+
+impl fmt::Display for BorrowMutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "already borrowed")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BorrowMutError {}
+
 #[allow(unreachable_code)]
 fn panic_abort(error: &str) -> ! {
     #[cfg(panic = "abort")]
@@ -234,6 +436,211 @@ fn panic_abort(error: &str) -> ! {
     }
 }
 
+/// Allows for obtaining references with `'static` lifetime via runtime borrow checking, in a
+/// single-threaded context. It offers the same borrowing, fallible borrowing and projection
+/// methods as [`ScopedReference`], but uses a `Cell`-based counter rather than an atomic,
+/// avoiding the cost of an atomic read-modify-write on every borrow and drop. It has no split
+/// mutable borrows (`borrow_mut_map`, `split`) and no weak borrows.
+pub struct LocalScopedReference<'a, T: ?Sized> {
+    reference: Result<&'a T, &'a mut T>,
+    alive: Rc<Cell<usize>>
+}
+
+impl<'a, T: ?Sized> LocalScopedReference<'a, T> {
+    /// Creates a new scoped reference for the specified borrow.
+    pub fn new(reference: &'a T) -> Self {
+        let alive = Rc::new(Cell::new(0));
+        let reference = Ok(reference);
+        Self { reference, alive }
+    }
+
+    /// Creates a new scoped reference for the specifed mutable borrow.
+    pub fn new_mut(reference: &'a mut T) -> Self {
+        let alive = Rc::new(Cell::new(0));
+        let reference = Err(reference);
+        Self { reference, alive }
+    }
+
+    /// Obtains a dynamically-checked borrow to the current reference.
+    ///
+    /// # Panics/Aborts
+    ///
+    /// Aborts the process if the reference is already borrowed mutably. See [`Self::try_borrow`]
+    /// for a version that returns an error instead.
+    pub fn borrow(&self) -> LocalScopedBorrow<T> {
+        match self.try_borrow() {
+            Ok(borrow) => borrow,
+            Err(_) => panic_abort("Cannot borrow a lifetime immutably while it is already borrowed mutably.")
+        }
+    }
+
+    /// Obtains a mutable dynamically-checked borrow to the current reference.
+    ///
+    /// # Panics/Aborts
+    ///
+    /// Aborts the process if the reference is already borrowed. See [`Self::try_borrow_mut`]
+    /// for a version that returns an error instead.
+    pub fn borrow_mut(&mut self) -> LocalScopedBorrowMut<T> {
+        match self.try_borrow_mut() {
+            Ok(borrow) => borrow,
+            Err(_) => panic_abort("Scoped lifetime is already borrowed.")
+        }
+    }
+
+    /// Attempts to obtain a dynamically-checked borrow to the current reference, returning
+    /// an error rather than aborting if the reference is already borrowed mutably.
+    pub fn try_borrow(&self) -> Result<LocalScopedBorrow<T>, BorrowError> {
+        let previous = self.alive.get();
+        if previous & MUTABLE_BORROW != 0 {
+            return Err(BorrowError { _private: () });
+        }
+        let next = previous + 1;
+        if next & MUTABLE_BORROW != 0 {
+            panic_abort("Too many outstanding shared borrows of a scoped lifetime.");
+        }
+        self.alive.set(next);
+
+        let pointer = match &self.reference {
+            Ok(r) => *r as *const T,
+            Err(r) => *r as *const T
+        };
+        Ok(LocalScopedBorrow { pointer, alive: self.alive.clone() })
+    }
+
+    /// Attempts to obtain a mutable dynamically-checked borrow to the current reference, returning
+    /// an error rather than aborting if the reference is already borrowed.
+    pub fn try_borrow_mut(&mut self) -> Result<LocalScopedBorrowMut<T>, BorrowMutError> {
+        if self.alive.get() != 0 {
+            Err(BorrowMutError { _private: () })
+        }
+        else {
+            self.alive.set(MUTABLE_BORROW);
+            Ok(LocalScopedBorrowMut { pointer: unsafe { self.reference.as_mut().map_err(|x| *x as *mut T).unwrap_err_unchecked() }, alive: self.alive.clone() })
+        }
+    }
+}
+
+impl<'a, T: ?Sized> fmt::Debug for LocalScopedReference<'a, T> {
+    fn fmt(&self, _: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Ok(())
+    }
+}
+
+impl<'a, T: ?Sized> fmt::Display for LocalScopedReference<'a, T> {
+    fn fmt(&self, _: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Ok(())
+    }
+}
+
+impl<'a, T: ?Sized> Drop for LocalScopedReference<'a, T> {
+    fn drop(&mut self) {
+        if self.alive.get() != 0 {
+            panic_abort("Scoped lifetime was dropped while a borrow was out.")
+        }
+    }
+}
+
+/// Represents a borrow with a runtime-checked lifetime, obtained from a [`LocalScopedReference`].
+pub struct LocalScopedBorrow<T: ?Sized> {
+    pointer: *const T,
+    alive: Rc<Cell<usize>>
+}
+
+impl<T: ?Sized> Deref for LocalScopedBorrow<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*self.pointer }
+    }
+}
+
+impl<T: ?Sized> Drop for LocalScopedBorrow<T> {
+    fn drop(&mut self) {
+        self.alive.set(self.alive.get() - 1);
+    }
+}
+
+impl<T: ?Sized> Clone for LocalScopedBorrow<T> {
+    fn clone(&self) -> Self {
+        self.alive.set(self.alive.get() + 1);
+        Self { pointer: self.pointer, alive: self.alive.clone() }
+    }
+}
+
+impl<T: ?Sized> LocalScopedBorrow<T> {
+    /// Projects this borrow to a subfield, preserving the runtime liveness guarantee on the original reference.
+    pub fn map<U: ?Sized>(self, f: impl FnOnce(&T) -> &U) -> LocalScopedBorrow<U> {
+        let pointer = f(&self) as *const U;
+        // SAFETY: `self` is forgotten immediately below, so its `Drop` impl never runs and
+        // the liveness count is left untouched - it is simply handed off to the new borrow.
+        let alive = unsafe { ptr::read(&self.alive) };
+        mem::forget(self);
+        LocalScopedBorrow { pointer, alive }
+    }
+}
+
+impl<T: fmt::Debug + ?Sized> fmt::Debug for LocalScopedBorrow<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: fmt::Display + ?Sized> fmt::Display for LocalScopedBorrow<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&**self, f)
+    }
+}
+
+/// Represents a mutable borrow with a runtime-checked lifetime, obtained from a [`LocalScopedReference`].
+pub struct LocalScopedBorrowMut<T: ?Sized> {
+    pointer: *mut T,
+    alive: Rc<Cell<usize>>
+}
+
+impl<T: ?Sized> Deref for LocalScopedBorrowMut<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*self.pointer }
+    }
+}
+
+impl<T: ?Sized> DerefMut for LocalScopedBorrowMut<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { &mut *self.pointer }
+    }
+}
+
+impl<T: ?Sized> LocalScopedBorrowMut<T> {
+    /// Projects this mutable borrow to a subfield, preserving the runtime liveness guarantee on the original reference.
+    pub fn map_mut<U: ?Sized>(mut self, f: impl FnOnce(&mut T) -> &mut U) -> LocalScopedBorrowMut<U> {
+        let pointer = f(&mut self) as *mut U;
+        // SAFETY: `self` is forgotten immediately below, so its `Drop` impl never runs and
+        // the mutable-borrow sentinel is left untouched - it is simply handed off to the new borrow.
+        let alive = unsafe { ptr::read(&self.alive) };
+        mem::forget(self);
+        LocalScopedBorrowMut { pointer, alive }
+    }
+}
+
+impl<T: ?Sized> Drop for LocalScopedBorrowMut<T> {
+    fn drop(&mut self) {
+        self.alive.set(self.alive.get() & !MUTABLE_BORROW);
+    }
+}
+
+impl<T: fmt::Debug + ?Sized> fmt::Debug for LocalScopedBorrowMut<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: fmt::Display + ?Sized> fmt::Display for LocalScopedBorrowMut<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&**self, f)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -263,4 +670,215 @@ mod tests {
         drop(static_borrow);
         drop(scoped_ref);
     }
+
+    #[test]
+    fn test_map() {
+        let x = (1, 2);
+        let scoped_ref = ScopedReference::new(&x);
+
+        let first = scoped_ref.borrow().map(|pair| &pair.0);
+        assert_eq!(*first, 1);
+
+        // Panic: first is still out!
+        // drop(scoped_ref);
+
+        drop(first);
+        drop(scoped_ref);
+    }
+
+    #[test]
+    fn test_map_mut() {
+        let mut x = (1, 2);
+        let mut scoped_ref = ScopedReference::new_mut(&mut x);
+
+        let mut second = scoped_ref.borrow_mut().map_mut(|pair| &mut pair.1);
+        *second = 9;
+        assert_eq!(*second, 9);
+
+        // Panic: second is still out!
+        // drop(scoped_ref);
+
+        drop(second);
+        drop(scoped_ref);
+        assert_eq!(x, (1, 9));
+    }
+
+    #[test]
+    fn test_try_borrow() {
+        let mut x = 10;
+        let mut scoped_ref = ScopedReference::new_mut(&mut x);
+
+        let mut_ref_to_x = scoped_ref.borrow_mut();
+        assert!(scoped_ref.try_borrow().is_err());
+        drop(mut_ref_to_x);
+
+        assert!(scoped_ref.try_borrow().is_ok());
+    }
+
+    #[test]
+    fn test_try_borrow_mut() {
+        let mut x = 10;
+        let mut scoped_ref = ScopedReference::new_mut(&mut x);
+
+        let borrow = scoped_ref.borrow();
+        assert!(scoped_ref.try_borrow_mut().is_err());
+        drop(borrow);
+
+        assert!(scoped_ref.try_borrow_mut().is_ok());
+    }
+
+    #[test]
+    fn test_local_borrow_mut() {
+        let mut x = 10;
+        let borrowed_x = &mut x;
+        let mut scoped_ref = LocalScopedReference::new_mut(borrowed_x);
+
+        let mut mut_ref_to_x = scoped_ref.borrow_mut();
+        *mut_ref_to_x = 9;
+
+        // Panic: mut_ref_to_x is still out!
+        // drop(scoped_ref);
+
+        drop(mut_ref_to_x);
+
+        let shared_borrow = scoped_ref.borrow();
+        assert_eq!(*shared_borrow, 9);
+
+        // Panic: shared_borrow is still out!
+        // drop(scoped_ref);
+
+        drop(shared_borrow);
+        drop(scoped_ref);
+    }
+
+    #[test]
+    fn test_local_try_borrow() {
+        let mut x = 10;
+        let mut scoped_ref = LocalScopedReference::new_mut(&mut x);
+
+        let mut_ref_to_x = scoped_ref.borrow_mut();
+        assert!(scoped_ref.try_borrow().is_err());
+        drop(mut_ref_to_x);
+
+        assert!(scoped_ref.try_borrow().is_ok());
+    }
+
+    #[test]
+    fn test_local_try_borrow_mut() {
+        let mut x = 10;
+        let mut scoped_ref = LocalScopedReference::new_mut(&mut x);
+
+        let borrow = scoped_ref.borrow();
+        assert!(scoped_ref.try_borrow_mut().is_err());
+        drop(borrow);
+
+        assert!(scoped_ref.try_borrow_mut().is_ok());
+    }
+
+    #[test]
+    fn test_local_map() {
+        let x = (1, 2);
+        let scoped_ref = LocalScopedReference::new(&x);
+
+        let first = scoped_ref.borrow().map(|pair| &pair.0);
+        assert_eq!(*first, 1);
+
+        // Panic: first is still out!
+        // drop(scoped_ref);
+
+        drop(first);
+        drop(scoped_ref);
+    }
+
+    #[test]
+    fn test_local_map_mut() {
+        let mut x = (1, 2);
+        let mut scoped_ref = LocalScopedReference::new_mut(&mut x);
+
+        let mut second = scoped_ref.borrow_mut().map_mut(|pair| &mut pair.1);
+        *second = 9;
+        assert_eq!(*second, 9);
+
+        // Panic: second is still out!
+        // drop(scoped_ref);
+
+        drop(second);
+        drop(scoped_ref);
+        assert_eq!(x, (1, 9));
+    }
+
+    #[test]
+    fn test_borrow_mut_map() {
+        let mut x = (1, 2);
+        let mut scoped_ref = ScopedReference::new_mut(&mut x);
+
+        let (mut first, mut second) = scoped_ref.borrow_mut_map(|pair| (&mut pair.0, &mut pair.1));
+        *first = 10;
+        *second = 20;
+
+        // Panic: first and second are still out!
+        // drop(scoped_ref);
+
+        drop(first);
+        assert!(scoped_ref.try_borrow().is_err());
+
+        drop(second);
+        drop(scoped_ref);
+        assert_eq!(x, (10, 20));
+    }
+
+    #[test]
+    fn test_split() {
+        let mut x = [1, 2, 3];
+        let mut scoped_ref = ScopedReference::new_mut(&mut x);
+
+        let [mut a, mut b, mut c] = scoped_ref.split(|array| {
+            let [a, b, c] = array;
+            [a, b, c]
+        });
+        *a = 10;
+        *b = 20;
+        *c = 30;
+
+        drop(a);
+        drop(b);
+        assert!(scoped_ref.try_borrow_mut().is_err());
+
+        drop(c);
+        drop(scoped_ref);
+        assert_eq!(x, [10, 20, 30]);
+    }
+
+    #[test]
+    fn test_weak_upgrade() {
+        let x = 10;
+        let scoped_ref = ScopedReference::new(&x);
+
+        let borrow = scoped_ref.borrow();
+        let weak = borrow.downgrade();
+        drop(borrow);
+
+        let upgraded = weak.upgrade().expect("reference is still alive");
+        assert_eq!(*upgraded, 10);
+        drop(upgraded);
+        drop(scoped_ref);
+
+        assert!(weak.upgrade().is_none());
+    }
+
+    #[test]
+    fn test_weak_upgrade_rejects_mutable_borrow() {
+        let mut x = 10;
+        let mut scoped_ref = ScopedReference::new_mut(&mut x);
+
+        let borrow = scoped_ref.borrow();
+        let weak = borrow.downgrade();
+        drop(borrow);
+
+        let mut_borrow = scoped_ref.borrow_mut();
+        assert!(weak.upgrade().is_none());
+        drop(mut_borrow);
+
+        assert!(weak.upgrade().is_some());
+    }
 }
\ No newline at end of file